@@ -10,7 +10,7 @@ pub fn init_panic_hook() {
 }
 
 // BookmarkNode structure
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BookmarkNode {
     pub id: String,
@@ -22,6 +22,12 @@ pub struct BookmarkNode {
     pub tags: Vec<String>,
     pub is_duplicate: bool,
     pub children: Vec<BookmarkNode>,
+    // Losing versions from a same-timestamp merge conflict, kept so nothing is silently dropped
+    #[serde(default)]
+    pub conflicts: Vec<BookmarkNode>,
+    // Bottom-up Merkle hash of this node's content, populated by `compute_hashes`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 impl BookmarkNode {
@@ -41,6 +47,8 @@ impl BookmarkNode {
             tags: Vec::new(),
             is_duplicate: false,
             children: Vec::new(),
+            conflicts: Vec::new(),
+            hash: None,
         }
     }
 
@@ -224,9 +232,76 @@ pub fn parse_html(html: &str) -> Result<JsValue, JsValue> {
     serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+// Normalize a URL for duplicate detection: unify http/https, lowercase host, strip default
+// ports, drop trailing slashes, remove common tracking query params and sort the remaining
+// ones. `exact_match` bypasses all of this and compares URLs byte-for-byte.
+fn normalize_url(url: &str, exact_match: bool) -> String {
+    if exact_match {
+        return url.to_string();
+    }
+
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_lowercase(), rest),
+        None => (String::new(), url),
+    };
+
+    let (host_and_port, path_and_query) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, ""),
+    };
+
+    let mut host_and_port = host_and_port.to_lowercase();
+    let default_port = match scheme.as_str() {
+        "http" => Some(":80"),
+        "https" => Some(":443"),
+        _ => None,
+    };
+    if let Some(port) = default_port.filter(|port| host_and_port.ends_with(port)) {
+        host_and_port.truncate(host_and_port.len() - port.len());
+    }
+
+    // http and https are the same site for duplicate-detection purposes
+    let comparison_scheme = if scheme == "https" { "http".to_string() } else { scheme };
+
+    let (path, query) = match path_and_query.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (path_and_query, None),
+    };
+    let path = path.trim_end_matches('/');
+
+    let mut kept_params: Vec<(String, String)> = Vec::new();
+    if let Some(query) = query {
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = match pair.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => (pair.to_string(), String::new()),
+            };
+            let key_lower = key.to_lowercase();
+            if key_lower.starts_with("utm_") || key_lower == "fbclid" || key_lower == "gclid" {
+                continue;
+            }
+            kept_params.push((key, value));
+        }
+    }
+    kept_params.sort();
+
+    let query_string = if kept_params.is_empty() {
+        String::new()
+    } else {
+        let joined = kept_params
+            .iter()
+            .map(|(k, v)| if v.is_empty() { k.clone() } else { format!("{}={}", k, v) })
+            .collect::<Vec<_>>()
+            .join("&");
+        format!("?{}", joined)
+    };
+
+    format!("{}://{}{}{}", comparison_scheme, host_and_port, path, query_string)
+}
+
 // Find duplicates by URL
 #[wasm_bindgen]
-pub fn find_duplicates(nodes_js: JsValue) -> Result<JsValue, JsValue> {
+pub fn find_duplicates(nodes_js: JsValue, exact_match: bool) -> Result<JsValue, JsValue> {
     let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
@@ -236,56 +311,296 @@ pub fn find_duplicates(nodes_js: JsValue) -> Result<JsValue, JsValue> {
 
     let mut url_map: HashMap<String, Vec<BookmarkNode>> = HashMap::new();
 
-    // Group bookmarks by URL
+    // Group bookmarks by normalized URL (or the raw URL in exact-match mode)
     for node in all_nodes {
         if let Some(url) = &node.url {
             url_map
-                .entry(url.clone())
-                .or_insert_with(Vec::new)
+                .entry(normalize_url(url, exact_match))
+                .or_default()
                 .push(node);
         }
     }
 
-    // Filter to only groups with duplicates
+    // Filter to only groups with duplicates; report the first node's raw URL as the group's URL
     let duplicates: Vec<DuplicateGroup> = url_map
         .into_iter()
         .filter(|(_, nodes)| nodes.len() > 1)
-        .map(|(url, nodes)| DuplicateGroup { url, nodes })
+        .map(|(_, nodes)| {
+            let url = nodes[0].url.clone().unwrap_or_default();
+            DuplicateGroup { url, nodes }
+        })
         .collect();
 
     serde_wasm_bindgen::to_value(&duplicates).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
-// Merge two bookmark trees
+// Tally of what a merge did, so the UI can present a real sync report
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub conflicted: usize,
+}
+
+// Merged tree plus the summary of how it was produced
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeResult {
+    pub nodes: Vec<BookmarkNode>,
+    pub summary: MergeSummary,
+}
+
+fn hash_str(s: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Stable identity that survives a resync from a different device, unlike the regenerated
+// `node_{counter}` id: a content hash for bookmarks, the path of ancestor titles for folders
+fn stable_id(node: &BookmarkNode, ancestor_path: &str) -> String {
+    match &node.url {
+        Some(url) => format!("bm:{:x}", hash_str(&format!("{}\u{0}{}", url, node.title))),
+        None => format!("{}/{}", ancestor_path, node.title),
+    }
+}
+
+fn count_subtree(node: &BookmarkNode) -> usize {
+    1 + node.children.iter().map(count_subtree).sum::<usize>()
+}
+
+fn union_tags(base_tags: &[String], target_tags: &[String]) -> (Vec<String>, bool) {
+    let mut union = base_tags.to_vec();
+    for tag in target_tags {
+        if !union.contains(tag) {
+            union.push(tag.clone());
+        }
+    }
+    let changed = union.len() != base_tags.len();
+    (union, changed)
+}
+
+// Merge two nodes that share a stable identity: folders recurse, bookmarks resolve last-writer-wins
+fn merge_pair(
+    mut base_node: BookmarkNode,
+    target_node: BookmarkNode,
+    path: &str,
+    summary: &mut MergeSummary,
+) -> BookmarkNode {
+    let (tags, tags_changed) = union_tags(&base_node.tags, &target_node.tags);
+
+    if base_node.is_folder() {
+        let before_child_count = base_node.children.len();
+        let child_path = format!("{}/{}", path, base_node.title);
+        base_node.children = merge_level(base_node.children, target_node.children, &child_path, summary);
+        let children_changed = base_node.children.len() != before_child_count;
+
+        base_node.tags = tags;
+        base_node.last_modified = base_node.last_modified.max(target_node.last_modified);
+        if tags_changed || children_changed {
+            summary.updated += 1;
+        }
+        return base_node;
+    }
+
+    // Bookmark: last-writer-wins by last_modified; on a true tie, keep one side and record the other
+    let target_wins = target_node.last_modified > base_node.last_modified;
+    let tied = target_node.last_modified == base_node.last_modified;
+    let fields_differ = base_node.title != target_node.title
+        || base_node.url != target_node.url
+        || base_node.icon != target_node.icon;
+
+    let mut winner = if target_wins { target_node.clone() } else { base_node.clone() };
+    winner.tags = tags;
+
+    if tied && fields_differ {
+        let loser = if target_wins { base_node } else { target_node };
+        winner.conflicts.push(loser);
+        summary.conflicted += 1;
+    } else if target_wins || tags_changed {
+        summary.updated += 1;
+    }
+
+    winner
+}
+
+// Merge one level of siblings, keyed by stable identity rather than position
+fn merge_level(
+    base: Vec<BookmarkNode>,
+    target: Vec<BookmarkNode>,
+    path: &str,
+    summary: &mut MergeSummary,
+) -> Vec<BookmarkNode> {
+    let mut target_by_id: HashMap<String, BookmarkNode> = HashMap::new();
+    for node in target {
+        target_by_id.insert(stable_id(&node, path), node);
+    }
+
+    let mut merged = Vec::new();
+    for base_node in base {
+        let id = stable_id(&base_node, path);
+        if let Some(target_node) = target_by_id.remove(&id) {
+            merged.push(merge_pair(base_node, target_node, path, summary));
+        } else {
+            merged.push(base_node);
+        }
+    }
+
+    // Anything left in target is new to base
+    let mut added_nodes: Vec<BookmarkNode> = target_by_id.into_values().collect();
+    for node in &added_nodes {
+        summary.added += count_subtree(node);
+    }
+    merged.append(&mut added_nodes);
+
+    merged
+}
+
+// Merge two bookmark trees conflict-free, keyed on stable node identity rather than position
 #[wasm_bindgen]
 pub fn merge_trees(base_js: JsValue, target_js: JsValue) -> Result<JsValue, JsValue> {
-    let mut base: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(base_js)
+    let base: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(base_js)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
     let target: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(target_js)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-    // Merge by extending base with target trees
-    base.extend(target);
+    let mut summary = MergeSummary::default();
+    let mut merged = merge_level(base, target, "", &mut summary);
+
+    // Still flag same-URL bookmarks that merged as distinct identities (e.g. differing titles)
+    mark_duplicates_in_tree(&mut merged, false);
+
+    let result = MergeResult { nodes: merged, summary };
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Content hash of a single node, assuming its children's `hash` fields are already populated
+fn node_content_hash(node: &BookmarkNode) -> String {
+    if node.is_folder() {
+        let child_hashes: Vec<&str> = node
+            .children
+            .iter()
+            .map(|c| c.hash.as_deref().unwrap_or(""))
+            .collect();
+        format!("{:x}", hash_str(&format!("folder\u{0}{}\u{0}{}", node.title, child_hashes.join(","))))
+    } else {
+        let mut tags = node.tags.clone();
+        tags.sort();
+        format!(
+            "{:x}",
+            hash_str(&format!(
+                "bm\u{0}{}\u{0}{}\u{0}{}\u{0}{}",
+                node.url.as_deref().unwrap_or(""),
+                node.title,
+                tags.join(","),
+                node.icon.as_deref().unwrap_or("")
+            ))
+        )
+    }
+}
+
+// Populate `hash` on every node bottom-up so identical subtrees can be recognized without
+// walking them
+fn compute_hashes_rec(nodes: Vec<BookmarkNode>) -> Vec<BookmarkNode> {
+    nodes
+        .into_iter()
+        .map(|mut node| {
+            node.children = compute_hashes_rec(node.children);
+            node.hash = Some(node_content_hash(&node));
+            node
+        })
+        .collect()
+}
+
+// Assign every node a Merkle hash: a leaf hashes its own content, a folder hashes its title
+// plus the ordered hashes of its children
+#[wasm_bindgen]
+pub fn compute_hashes(nodes_js: JsValue) -> Result<JsValue, JsValue> {
+    let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let hashed = compute_hashes_rec(nodes);
+    serde_wasm_bindgen::to_value(&hashed).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Result of comparing two hashed trees
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeDiff {
+    pub added: Vec<BookmarkNode>,
+    pub removed: Vec<BookmarkNode>,
+    pub changed: Vec<BookmarkNode>,
+}
+
+// Check whether every node in the tree (and its descendants) already carries a `hash`
+fn all_hashed(nodes: &[BookmarkNode]) -> bool {
+    nodes.iter().all(|n| n.hash.is_some() && all_hashed(&n.children))
+}
+
+// Diff two already-hashed sibling lists by node id, short-circuiting into identical subtrees.
+fn diff_level(a: &[BookmarkNode], b: &[BookmarkNode], diff: &mut TreeDiff) {
+    let a_by_id: HashMap<&str, &BookmarkNode> = a.iter().map(|n| (n.id.as_str(), n)).collect();
+    let b_by_id: HashMap<&str, &BookmarkNode> = b.iter().map(|n| (n.id.as_str(), n)).collect();
+
+    for node in a {
+        if !b_by_id.contains_key(node.id.as_str()) {
+            diff.removed.push(node.clone());
+        }
+    }
+
+    for node in b {
+        match a_by_id.get(node.id.as_str()) {
+            None => diff.added.push(node.clone()),
+            Some(old) => {
+                let unchanged = matches!((&old.hash, &node.hash), (Some(a), Some(b)) if a == b);
+                if !unchanged {
+                    diff.changed.push((*node).clone());
+                    diff_level(&old.children, &node.children, diff);
+                }
+            }
+        }
+    }
+}
+
+// Diff two bookmark trees, proportional to the number of actual changes rather than total size:
+// subtrees whose root hash matches are skipped without being walked. Trees must already carry
+// `hash` fields from a prior `compute_hashes` call - this does not recompute them, so the cost
+// of hashing is paid once per edit rather than once per diff. A tree with any unhashed node
+// would otherwise silently diff as "everything changed", so that case is rejected up front.
+#[wasm_bindgen]
+pub fn diff_trees(a_js: JsValue, b_js: JsValue) -> Result<JsValue, JsValue> {
+    let a: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(a_js)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let b: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(b_js)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    if !all_hashed(&a) || !all_hashed(&b) {
+        return Err(JsValue::from_str(
+            "diff_trees requires both trees to be hashed first via compute_hashes",
+        ));
+    }
 
-    // Mark duplicates recursively
-    mark_duplicates_in_tree(&mut base);
+    let mut diff = TreeDiff::default();
+    diff_level(&a, &b, &mut diff);
 
-    serde_wasm_bindgen::to_value(&base).map_err(|e| JsValue::from_str(&e.to_string()))
+    serde_wasm_bindgen::to_value(&diff).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
 // Helper function to mark duplicates in tree
-fn mark_duplicates_in_tree(nodes: &mut Vec<BookmarkNode>) {
+fn mark_duplicates_in_tree(nodes: &mut [BookmarkNode], exact_match: bool) {
     // Collect all nodes
     let mut all_nodes = Vec::new();
     collect_all_nodes(nodes, &mut all_nodes);
 
-    // Build URL map
+    // Build normalized-URL map
     let mut url_map: HashMap<String, Vec<String>> = HashMap::new();
     for node in &all_nodes {
         if let Some(url) = &node.url {
             url_map
-                .entry(url.clone())
-                .or_insert_with(Vec::new)
+                .entry(normalize_url(url, exact_match))
+                .or_default()
                 .push(node.id.clone());
         }
     }
@@ -311,62 +626,286 @@ fn mark_node_as_duplicate(nodes: &mut [BookmarkNode], target_id: &str) {
     }
 }
 
-// Search nodes by query
-#[wasm_bindgen]
-pub fn search_nodes(nodes_js: JsValue, query: &str) -> Result<JsValue, JsValue> {
-    let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+// A single scored search hit, returned instead of a bare ID so the UI can render relevance order
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub id: String,
+    pub score: f64,
+}
 
-    let query_lower = query.to_lowercase();
-    let mut results = Vec::new();
+// Field a token was indexed from; used for the field-weight ranking layer
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SearchField {
+    Title,
+    Tag,
+    Url,
+}
 
-    // Recursive search function
-    fn search_recursive(nodes: &[BookmarkNode], query: &str, results: &mut Vec<String>) {
-        // Check for tag-specific search
-        let is_tag_search = query.starts_with("tag:");
-        let tag_query = if is_tag_search {
-            query.strip_prefix("tag:").unwrap_or("")
-        } else {
-            ""
-        };
+impl SearchField {
+    fn weight(self) -> u32 {
+        match self {
+            SearchField::Title => 3,
+            SearchField::Tag => 2,
+            SearchField::Url => 1,
+        }
+    }
+}
 
-        for node in nodes {
-            let matches = if is_tag_search {
-                // Tag-specific search
-                node.tags.iter().any(|t| t.to_lowercase().contains(tag_query))
-            } else {
-                // General search across title, URL, and tags
-                let title_match = node.title.to_lowercase().contains(query);
-                let url_match = node
-                    .url
-                    .as_ref()
-                    .map(|u| u.to_lowercase().contains(query))
-                    .unwrap_or(false);
-                let tag_match = node.tags.iter().any(|t| t.to_lowercase().contains(query));
+// One occurrence of an indexed token: which node, which field, and (for title tokens) its
+// word position so matched terms can be ranked by proximity
+#[derive(Clone, Debug)]
+struct TokenHit {
+    node_id: String,
+    field: SearchField,
+    title_position: Option<usize>,
+}
+
+// Strip common Latin diacritics so "resume" also matches "résumé"
+fn strip_diacritics(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ñ' => 'n',
+        'ç' => 'c',
+        other => other,
+    }
+}
+
+// Normalize a single token: lowercase and strip diacritics
+fn normalize_token(token: &str) -> String {
+    token.to_lowercase().chars().map(strip_diacritics).collect()
+}
+
+// Split text into lowercase, diacritic-stripped tokens on whitespace and punctuation
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || (c.is_ascii_punctuation() && c != '\''))
+        .map(normalize_token)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+// Split a URL into its host and path segments for indexing ("https://example.com/a/b" -> ["example", "com", "a", "b"])
+fn tokenize_url(url: &str) -> Vec<String> {
+    let without_scheme = url.split("://").last().unwrap_or(url);
+    tokenize(without_scheme)
+}
+
+// Classic Levenshtein edit distance, bailing out early once it exceeds `max` (pruned scan)
+fn levenshtein_within(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        let mut row_min = curr[0];
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            row_min = row_min.min(curr[j]);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    let distance = prev[b.len()];
+    if distance <= max { Some(distance) } else { None }
+}
+
+// Edit-distance budget for fuzzy term matching: tighter for short terms to avoid noisy matches
+fn fuzzy_threshold(term: &str) -> usize {
+    if term.chars().count() <= 5 { 1 } else { 2 }
+}
 
-                title_match || url_match || tag_match
+// Per-node accumulator while scoring a query against the inverted index
+#[derive(Default)]
+struct NodeMatch {
+    matched_terms: std::collections::HashSet<usize>,
+    exact_terms: std::collections::HashSet<usize>,
+    field_weight_sum: u32,
+    title_positions: Vec<usize>,
+}
+
+fn collect_tag_matches(nodes: &[BookmarkNode], tag_query: &str, results: &mut Vec<SearchResult>) {
+    for node in nodes {
+        if node.tags.iter().any(|t| t.to_lowercase().contains(tag_query)) {
+            results.push(SearchResult { id: node.id.clone(), score: 1.0 });
+        }
+        collect_tag_matches(&node.children, tag_query, results);
+    }
+}
+
+// Ranked, typo-tolerant full-text search over title, URL and tags. Plain function so the
+// scoring logic can be unit-tested without going through `JsValue`.
+fn rank_search(nodes: &[BookmarkNode], query: &str) -> Vec<SearchResult> {
+    // Tag-specific search keeps its old substring behavior - it's an exact filter, not a ranked query
+    if let Some(tag_query) = query.strip_prefix("tag:") {
+        let tag_query = tag_query.to_lowercase();
+        let mut results = Vec::new();
+        collect_tag_matches(nodes, &tag_query, &mut results);
+        return results;
+    }
+
+    // Build the inverted index: normalized token -> every node/field occurrence
+    let mut index: HashMap<String, Vec<TokenHit>> = HashMap::new();
+
+    fn index_node(node: &BookmarkNode, index: &mut HashMap<String, Vec<TokenHit>>) {
+        for (position, token) in tokenize(&node.title).into_iter().enumerate() {
+            index.entry(token).or_default().push(TokenHit {
+                node_id: node.id.clone(),
+                field: SearchField::Title,
+                title_position: Some(position),
+            });
+        }
+        if let Some(url) = &node.url {
+            for token in tokenize_url(url) {
+                index.entry(token).or_default().push(TokenHit {
+                    node_id: node.id.clone(),
+                    field: SearchField::Url,
+                    title_position: None,
+                });
+            }
+        }
+        for tag in &node.tags {
+            for token in tokenize(tag) {
+                index.entry(token).or_default().push(TokenHit {
+                    node_id: node.id.clone(),
+                    field: SearchField::Tag,
+                    title_position: None,
+                });
+            }
+        }
+        for child in &node.children {
+            index_node(child, index);
+        }
+    }
+
+    for node in nodes {
+        index_node(node, &mut index);
+    }
+
+    let query_terms = tokenize(query);
+    if query_terms.is_empty() {
+        return Vec::new();
+    }
+
+    // Best match per (query term, node): lowest edit distance, ties broken by field weight.
+    // A term can match many index tokens (e.g. "cat" fuzzily matches "bat"/"cot"/"mat"/...) but
+    // must only count once per node, or field weight and title proximity get inflated per token
+    // instead of per term.
+    #[derive(Clone, Copy)]
+    struct BestHit {
+        distance: usize,
+        field: SearchField,
+        title_position: Option<usize>,
+    }
+
+    fn is_better(candidate: &BestHit, current: &BestHit) -> bool {
+        (candidate.distance, std::cmp::Reverse(candidate.field.weight()))
+            < (current.distance, std::cmp::Reverse(current.field.weight()))
+    }
+
+    let mut best: HashMap<(usize, String), BestHit> = HashMap::new();
+
+    for (term_index, term) in query_terms.iter().enumerate() {
+        let threshold = fuzzy_threshold(term);
+        for (token, hits) in &index {
+            let distance = if token == term {
+                Some(0)
+            } else {
+                levenshtein_within(term, token, threshold)
             };
 
-            if matches {
-                results.push(node.id.clone());
+            let Some(distance) = distance else { continue };
+
+            for hit in hits {
+                let candidate = BestHit { distance, field: hit.field, title_position: hit.title_position };
+                best.entry((term_index, hit.node_id.clone()))
+                    .and_modify(|current| {
+                        if is_better(&candidate, current) {
+                            *current = candidate;
+                        }
+                    })
+                    .or_insert(candidate);
             }
+        }
+    }
 
-            // Recurse into children
-            search_recursive(&node.children, query, results);
+    let mut node_matches: HashMap<String, NodeMatch> = HashMap::new();
+    for ((term_index, node_id), hit) in best {
+        let entry = node_matches.entry(node_id).or_default();
+        entry.matched_terms.insert(term_index);
+        if hit.distance == 0 {
+            entry.exact_terms.insert(term_index);
+        }
+        entry.field_weight_sum += hit.field.weight();
+        if let Some(position) = hit.title_position {
+            entry.title_positions.push(position);
         }
     }
 
-    search_recursive(&nodes, &query_lower, &mut results);
+    // Proximity bonus: how close together the matched terms sit in the title (lower spread is better)
+    fn title_proximity_penalty(positions: &[usize]) -> u32 {
+        if positions.len() < 2 {
+            return 0;
+        }
+        let min = *positions.iter().min().unwrap();
+        let max = *positions.iter().max().unwrap();
+        (max - min) as u32
+    }
 
-    serde_wasm_bindgen::to_value(&results).map_err(|e| JsValue::from_str(&e.to_string()))
+    let mut results: Vec<SearchResult> = node_matches
+        .into_iter()
+        .map(|(id, m)| {
+            let score = (m.matched_terms.len() as f64) * 1_000_000.0
+                + (m.exact_terms.len() as f64) * 10_000.0
+                + (m.field_weight_sum as f64) * 100.0
+                - (title_proximity_penalty(&m.title_positions) as f64);
+            SearchResult { id, score }
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+    results
 }
 
-// Export to HTML (Netscape format)
+// Search nodes by query: ranked, typo-tolerant full-text search over title, URL and tags
 #[wasm_bindgen]
-pub fn serialize_to_html(nodes_js: JsValue) -> Result<String, JsValue> {
+pub fn search_nodes(nodes_js: JsValue, query: &str) -> Result<JsValue, JsValue> {
     let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
+    let results = rank_search(&nodes, query);
+
+    serde_wasm_bindgen::to_value(&results).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Escape text appearing between HTML tags
+fn escape_html_text(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// Escape text appearing inside a double-quoted HTML attribute
+fn escape_html_attr(s: &str) -> String {
+    escape_html_text(s).replace('"', "&quot;")
+}
+
+// Render bookmarks as Netscape-format HTML. Plain function so the escaping can be unit-tested
+// without going through `JsValue`.
+fn render_html(nodes: &[BookmarkNode]) -> String {
     let mut html = String::from("<!DOCTYPE NETSCAPE-Bookmark-file-1>\n");
     html.push_str("<META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n");
     html.push_str("<TITLE>Bookmarks</TITLE>\n");
@@ -379,7 +918,7 @@ pub fn serialize_to_html(nodes_js: JsValue) -> Result<String, JsValue> {
             if node.is_folder() {
                 html.push_str(&format!(
                     "{}<DT><H3 ADD_DATE=\"{}\">{}</H3>\n",
-                    indent_str, node.add_date, node.title
+                    indent_str, node.add_date, escape_html_text(&node.title)
                 ));
                 html.push_str(&format!("{}<DL><p>\n", indent_str));
                 serialize_nodes(&node.children, html, indent + 1);
@@ -388,42 +927,52 @@ pub fn serialize_to_html(nodes_js: JsValue) -> Result<String, JsValue> {
                 let icon_attr = node
                     .icon
                     .as_ref()
-                    .map(|i| format!(" ICON=\"{}\"", i))
+                    .map(|i| format!(" ICON=\"{}\"", escape_html_attr(i)))
                     .unwrap_or_default();
                 html.push_str(&format!(
                     "{}<DT><A HREF=\"{}\" ADD_DATE=\"{}\"{}>{}</A>\n",
-                    indent_str, url, node.add_date, icon_attr, node.title
+                    indent_str,
+                    escape_html_attr(url),
+                    node.add_date,
+                    icon_attr,
+                    escape_html_text(&node.title)
                 ));
             }
         }
     }
 
-    serialize_nodes(&nodes, &mut html, 1);
+    serialize_nodes(nodes, &mut html, 1);
     html.push_str("</DL><p>\n");
 
-    Ok(html)
+    html
 }
 
-// Export to JSON
+// Export to HTML (Netscape format)
 #[wasm_bindgen]
-pub fn serialize_to_json(nodes_js: JsValue) -> Result<String, JsValue> {
+pub fn serialize_to_html(nodes_js: JsValue) -> Result<String, JsValue> {
     let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-    serde_json::to_string_pretty(&nodes).map_err(|e| JsValue::from_str(&e.to_string()))
+    Ok(render_html(&nodes))
 }
 
-// Export to CSV
+// Export to JSON
 #[wasm_bindgen]
-pub fn serialize_to_csv(nodes_js: JsValue) -> Result<String, JsValue> {
+pub fn serialize_to_json(nodes_js: JsValue) -> Result<String, JsValue> {
     let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
+    serde_json::to_string_pretty(&nodes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Render bookmarks as CSV. Plain function so the escaping can be unit-tested without going
+// through `JsValue`.
+fn render_csv(nodes: &[BookmarkNode]) -> String {
     let mut csv = String::from("Title,URL,Add Date,Tags,Type\n");
 
     // Flatten tree first
     let mut all_nodes = Vec::new();
-    collect_all_nodes(&nodes, &mut all_nodes);
+    collect_all_nodes(nodes, &mut all_nodes);
 
     for node in all_nodes {
         let url = node.url.as_deref().unwrap_or("");
@@ -433,14 +982,23 @@ pub fn serialize_to_csv(nodes_js: JsValue) -> Result<String, JsValue> {
         csv.push_str(&format!(
             "\"{}\",\"{}\",{},\"{}\",\"{}\"\n",
             node.title.replace("\"", "\"\""),
-            url,
+            url.replace("\"", "\"\""),
             node.add_date,
-            tags,
+            tags.replace("\"", "\"\""),
             node_type
         ));
     }
 
-    Ok(csv)
+    csv
+}
+
+// Export to CSV
+#[wasm_bindgen]
+pub fn serialize_to_csv(nodes_js: JsValue) -> Result<String, JsValue> {
+    let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    Ok(render_csv(&nodes))
 }
 
 // Export to Markdown
@@ -451,15 +1009,28 @@ pub fn serialize_to_markdown(nodes_js: JsValue) -> Result<String, JsValue> {
 
     let mut md = String::from("# Bookmarks\n\n");
 
+    // Escape characters that would otherwise break the `[title](url)` link syntax
+    fn escape_markdown_text(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('[', "\\[").replace(']', "\\]")
+    }
+    fn escape_markdown_url(s: &str) -> String {
+        s.replace(' ', "%20").replace('(', "%28").replace(')', "%29")
+    }
+
     fn build_markdown(nodes: &[BookmarkNode], md: &mut String, level: usize) {
         let indent = "  ".repeat(level);
 
         for node in nodes {
             if node.is_folder() {
-                md.push_str(&format!("{}## {}\n\n", indent, node.title));
+                md.push_str(&format!("{}## {}\n\n", indent, escape_markdown_text(&node.title)));
                 build_markdown(&node.children, md, level + 1);
             } else if let Some(url) = &node.url {
-                md.push_str(&format!("{}- [{}]({})\n", indent, node.title, url));
+                md.push_str(&format!(
+                    "{}- [{}]({})\n",
+                    indent,
+                    escape_markdown_text(&node.title),
+                    escape_markdown_url(url)
+                ));
             }
         }
     }
@@ -594,19 +1165,158 @@ pub fn remove_tag(nodes_js: JsValue, node_id: &str, tag: &str) -> Result<JsValue
     serde_wasm_bindgen::to_value(&nodes).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
-// Move a node to a new parent
+// A tag's usage across the tree, for rendering a tag cloud and spotting orphaned tags
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TagStat {
+    pub tag: String,
+    pub count: usize,
+    pub node_ids: Vec<String>,
+}
+
+// Count how often each tag is used and which nodes carry it, most-used first
 #[wasm_bindgen]
-pub fn move_node(
-    nodes_js: JsValue,
-    node_id: &str,
-    new_parent_id: &str,
-) -> Result<JsValue, JsValue> {
+pub fn tag_statistics(nodes_js: JsValue) -> Result<JsValue, JsValue> {
+    let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let mut all_nodes = Vec::new();
+    collect_all_nodes(&nodes, &mut all_nodes);
+
+    let mut by_tag: HashMap<String, Vec<String>> = HashMap::new();
+    for node in &all_nodes {
+        for tag in &node.tags {
+            by_tag.entry(tag.clone()).or_default().push(node.id.clone());
+        }
+    }
+
+    let mut stats: Vec<TagStat> = by_tag
+        .into_iter()
+        .map(|(tag, node_ids)| TagStat { tag, count: node_ids.len(), node_ids })
+        .collect();
+    stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+
+    serde_wasm_bindgen::to_value(&stats).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Extract the host from a URL, lowercased, with the port and a leading "www." stripped
+fn extract_host(url: &str) -> Option<String> {
+    let after_scheme = url.split("://").nth(1).unwrap_or(url);
+    let host_and_rest = after_scheme.split(['/', '?', '#']).next()?;
+    let host_no_port = host_and_rest.split(':').next()?.to_lowercase();
+    let host = host_no_port.strip_prefix("www.").unwrap_or(&host_no_port);
+    if host.is_empty() { None } else { Some(host.to_string()) }
+}
+
+// Common two-label public suffixes where the registrable name is the label *before* both parts,
+// e.g. "bbc.co.uk" -> "bbc", not "co". Not an exhaustive public suffix list, just the common cases.
+const MULTI_PART_SUFFIXES: &[&str] = &[
+    "co.uk", "org.uk", "gov.uk", "ac.uk", "me.uk",
+    "co.jp", "co.kr", "co.nz", "co.in", "co.za", "co.il",
+    "com.au", "net.au", "org.au", "com.br", "com.cn", "com.mx",
+];
+
+// Normalize a host down to its registrable name for tagging, e.g. "docs.github.com" -> "github"
+fn registrable_domain_tag(host: &str) -> String {
+    let labels: Vec<&str> = host.split('.').collect();
+
+    if labels.len() >= 3 {
+        let last_two = format!("{}.{}", labels[labels.len() - 2], labels[labels.len() - 1]);
+        if MULTI_PART_SUFFIXES.contains(&last_two.as_str()) {
+            return labels[labels.len() - 3].to_string();
+        }
+    }
+
+    if labels.len() >= 2 {
+        labels[labels.len() - 2].to_string()
+    } else {
+        host.to_string()
+    }
+}
+
+// Common hosts grouped into a higher-level tag, e.g. "github" and "gitlab" both mean "code"
+fn domain_group_tag(domain_tag: &str) -> Option<&'static str> {
+    match domain_tag {
+        "youtube" | "vimeo" => Some("video"),
+        "github" | "gitlab" => Some("code"),
+        _ => None,
+    }
+}
+
+// Add a normalized domain tag (and, where applicable, a higher-level group tag) to every
+// bookmark that doesn't already have one, so an unstructured dump gets instant organization
+#[wasm_bindgen]
+pub fn auto_tag_by_domain(nodes_js: JsValue) -> Result<JsValue, JsValue> {
     let mut nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-    // Find and remove the node from its current location
-    let mut moved_node: Option<BookmarkNode> = None;
-    
+    fn walk(nodes: &mut [BookmarkNode]) {
+        for node in nodes {
+            if let Some(url) = node.url.clone() {
+                if let Some(host) = extract_host(&url) {
+                    let domain_tag = registrable_domain_tag(&host);
+                    if !node.tags.contains(&domain_tag) {
+                        node.tags.push(domain_tag.clone());
+                    }
+                    if let Some(group) = domain_group_tag(&domain_tag) {
+                        if !node.tags.iter().any(|t| t == group) {
+                            node.tags.push(group.to_string());
+                        }
+                    }
+                }
+            }
+            walk(&mut node.children);
+        }
+    }
+
+    walk(&mut nodes);
+
+    serde_wasm_bindgen::to_value(&nodes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Find a node by ID (immutable)
+fn find_node_by_id<'a>(nodes: &'a [BookmarkNode], target_id: &str) -> Option<&'a BookmarkNode> {
+    for node in nodes {
+        if node.id == target_id {
+            return Some(node);
+        }
+        if let Some(found) = find_node_by_id(&node.children, target_id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn collect_ids(node: &BookmarkNode, ids: &mut std::collections::HashSet<String>) {
+    ids.insert(node.id.clone());
+    for child in &node.children {
+        collect_ids(child, ids);
+    }
+}
+
+// Move a node to a new parent in place. Validates the move against the tree *before* mutating
+// anything, so a bad `new_parent_id` can never fall back to silently corrupting the hierarchy.
+// Plain function so the validation guards can be unit-tested without going through `JsValue`.
+fn move_node_in_tree(nodes: &mut Vec<BookmarkNode>, node_id: &str, new_parent_id: &str) -> Result<(), String> {
+    let moving_to_root = new_parent_id == "root" || new_parent_id.is_empty();
+
+    let node_to_move_ref = find_node_by_id(nodes, node_id)
+        .ok_or_else(|| format!("Node with id '{}' not found", node_id))?;
+
+    if !moving_to_root {
+        if new_parent_id == node_id {
+            return Err("Cannot move a node into itself".to_string());
+        }
+        let mut subtree_ids = std::collections::HashSet::new();
+        collect_ids(node_to_move_ref, &mut subtree_ids);
+        if subtree_ids.contains(new_parent_id) {
+            return Err("Cannot move a node into its own descendant".to_string());
+        }
+        if find_node_by_id(nodes, new_parent_id).is_none() {
+            return Err(format!("Parent node with id '{}' not found", new_parent_id));
+        }
+    }
+
     // Helper function to remove node from tree
     fn remove_node_recursive(nodes: &mut Vec<BookmarkNode>, target_id: &str, result: &mut Option<BookmarkNode>) -> bool {
         for i in (0..nodes.len()).rev() {
@@ -621,27 +1331,428 @@ pub fn move_node(
         false
     }
 
-    if !remove_node_recursive(&mut nodes, node_id, &mut moved_node) {
-        return Err(JsValue::from_str(&format!("Node with id '{}' not found", node_id)));
-    }
-
+    let mut moved_node: Option<BookmarkNode> = None;
+    remove_node_recursive(nodes, node_id, &mut moved_node);
     let node_to_move = moved_node.unwrap();
 
-    // Add node to new parent
-    if new_parent_id == "root" || new_parent_id.is_empty() {
-        // Add to root level
+    if moving_to_root {
         nodes.push(node_to_move);
     } else {
-        // Find new parent and add to its children
-        if let Some(parent) = find_node_by_id_mut(&mut nodes, new_parent_id) {
-            parent.children.push(node_to_move);
+        // Already verified to exist above, so this is infallible
+        find_node_by_id_mut(nodes, new_parent_id)
+            .unwrap()
+            .children
+            .push(node_to_move);
+    }
+
+    Ok(())
+}
+
+// Move a node to a new parent
+#[wasm_bindgen]
+pub fn move_node(
+    nodes_js: JsValue,
+    node_id: &str,
+    new_parent_id: &str,
+) -> Result<JsValue, JsValue> {
+    let mut nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    move_node_in_tree(&mut nodes, node_id, new_parent_id).map_err(|e| JsValue::from_str(&e))?;
+
+    serde_wasm_bindgen::to_value(&nodes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// A single structural or data problem found while validating the tree
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub node_id: String,
+    pub kind: String,
+    pub message: String,
+}
+
+// Full validation report: never mutates the tree, just describes what's wrong with it
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+fn is_http_url(url: &str) -> bool {
+    let lower = url.trim().to_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+fn walk_validate(
+    nodes: &[BookmarkNode],
+    seen_ids: &mut std::collections::HashSet<String>,
+    link_statuses: &HashMap<String, u16>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for node in nodes {
+        // A repeated id can only happen if the hierarchy was corrupted (e.g. by a bad move)
+        if !seen_ids.insert(node.id.clone()) {
+            issues.push(ValidationIssue {
+                node_id: node.id.clone(),
+                kind: "duplicate_id".to_string(),
+                message: "Node id appears more than once in the tree".to_string(),
+            });
+        }
+
+        if node.title.trim().is_empty() {
+            issues.push(ValidationIssue {
+                node_id: node.id.clone(),
+                kind: "empty_title".to_string(),
+                message: "Node has an empty title".to_string(),
+            });
+        }
+
+        if node.is_folder() {
+            if !node.children.is_empty() && node.children.iter().all(|c| c.is_duplicate) {
+                issues.push(ValidationIssue {
+                    node_id: node.id.clone(),
+                    kind: "only_duplicates".to_string(),
+                    message: "Folder contains only duplicate bookmarks".to_string(),
+                });
+            }
         } else {
-            // Parent not found, restore node to original position and return error
-            // We can't easily restore, so just add to root as fallback
-            nodes.push(node_to_move);
-            return Err(JsValue::from_str(&format!("Parent node with id '{}' not found", new_parent_id)));
+            let url = node.url.as_deref().unwrap_or("").trim();
+            if url.is_empty() {
+                issues.push(ValidationIssue {
+                    node_id: node.id.clone(),
+                    kind: "empty_url".to_string(),
+                    message: "Bookmark has no URL".to_string(),
+                });
+            } else if url.to_lowercase().starts_with("javascript:") {
+                issues.push(ValidationIssue {
+                    node_id: node.id.clone(),
+                    kind: "javascript_uri".to_string(),
+                    message: "Bookmark URL is a javascript: URI".to_string(),
+                });
+            } else if !is_http_url(url) {
+                issues.push(ValidationIssue {
+                    node_id: node.id.clone(),
+                    kind: "invalid_scheme".to_string(),
+                    message: format!("Bookmark URL '{}' is not http(s)", url),
+                });
+            } else if let Some(status) = link_statuses.get(url) {
+                if *status >= 400 {
+                    issues.push(ValidationIssue {
+                        node_id: node.id.clone(),
+                        kind: "dead_link".to_string(),
+                        message: format!("URL returned HTTP {}", status),
+                    });
+                }
+            }
+
+            if node.is_duplicate {
+                issues.push(ValidationIssue {
+                    node_id: node.id.clone(),
+                    kind: "orphaned_duplicate".to_string(),
+                    message: "Bookmark is a lingering duplicate copy".to_string(),
+                });
+            }
         }
+
+        walk_validate(&node.children, seen_ids, link_statuses, issues);
     }
+}
 
-    serde_wasm_bindgen::to_value(&nodes).map_err(|e| JsValue::from_str(&e.to_string()))
+// Audit the tree for structural and data problems without mutating it. `link_statuses_js` is
+// an optional map of URL -> HTTP status, since WASM can't fetch itself; pass `undefined`/`null`
+// to skip dead-link checks and get structural issues only.
+#[wasm_bindgen]
+pub fn validate_tree(nodes_js: JsValue, link_statuses_js: JsValue) -> Result<JsValue, JsValue> {
+    let nodes: Vec<BookmarkNode> = serde_wasm_bindgen::from_value(nodes_js)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let link_statuses: HashMap<String, u16> = if link_statuses_js.is_undefined() || link_statuses_js.is_null() {
+        HashMap::new()
+    } else {
+        serde_wasm_bindgen::from_value(link_statuses_js).map_err(|e| JsValue::from_str(&e.to_string()))?
+    };
+
+    let mut issues = Vec::new();
+    let mut seen_ids = std::collections::HashSet::new();
+    walk_validate(&nodes, &mut seen_ids, &link_statuses, &mut issues);
+
+    let report = ValidationReport { issues };
+    serde_wasm_bindgen::to_value(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmark(id: &str, title: &str, url: &str) -> BookmarkNode {
+        BookmarkNode::new(id.to_string(), title.to_string(), Some(url.to_string()), 0)
+    }
+
+    fn folder(id: &str, title: &str) -> BookmarkNode {
+        BookmarkNode::new(id.to_string(), title.to_string(), None, 0)
+    }
+
+    #[test]
+    fn rank_search_scores_each_term_once_per_node_not_once_per_fuzzy_token() {
+        // "cat" fuzzily matches "bat"/"cot"/"mat"/"rat"/"hat" (distance 1) as well as the
+        // literal "cat" token (distance 0) - all six tokens live in the title field, so a naive
+        // per-token sum would inflate both the field-weight score and the title proximity tier.
+        let nodes = vec![bookmark("n1", "cat bat cot mat rat hat", "https://example.com/a")];
+
+        let results = rank_search(&nodes, "cat");
+
+        assert_eq!(results.len(), 1);
+        // One matched term (1_000_000) + one exact match (10_000) + one title hit (3 * 100) with
+        // no proximity penalty (only one position recorded for the single term).
+        assert_eq!(results[0].score, 1_010_300.0);
+    }
+
+    #[test]
+    fn rank_search_is_typo_tolerant() {
+        let nodes = vec![bookmark("n1", "JavaScript guide", "https://example.com/js")];
+        let results = rank_search(&nodes, "javscript");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "n1");
+    }
+
+    #[test]
+    fn merge_pair_records_conflict_on_timestamp_tie_with_differing_fields() {
+        let mut base = bookmark("base_id", "Example", "https://example.com");
+        base.last_modified = 100;
+        base.tags = vec!["a".to_string()];
+
+        let mut target = bookmark("target_id", "Example", "https://example.com");
+        target.last_modified = 100;
+        target.icon = Some("icon.png".to_string());
+        target.tags = vec!["b".to_string()];
+
+        let mut summary = MergeSummary::default();
+        let merged = merge_pair(base, target, "", &mut summary);
+
+        assert_eq!(summary.conflicted, 1);
+        assert_eq!(merged.conflicts.len(), 1);
+        // Tags are unioned regardless of which side is kept as the winner
+        assert!(merged.tags.contains(&"a".to_string()));
+        assert!(merged.tags.contains(&"b".to_string()));
+    }
+
+    #[test]
+    fn merge_pair_last_writer_wins_on_distinct_timestamps() {
+        let mut base = bookmark("base_id", "Old Title", "https://example.com");
+        base.last_modified = 100;
+
+        let mut target = bookmark("target_id", "New Title", "https://example.com");
+        target.last_modified = 200;
+
+        let mut summary = MergeSummary::default();
+        let merged = merge_pair(base, target, "", &mut summary);
+
+        assert_eq!(merged.title, "New Title");
+        assert_eq!(summary.updated, 1);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn diff_level_short_circuits_on_matching_hash() {
+        let mut a = bookmark("n1", "Same", "https://example.com");
+        a.hash = Some("same-hash".to_string());
+        a.children.push(bookmark("child", "Untouched", "https://example.com/child"));
+
+        let mut b = a.clone();
+        // If the diff recursed into children despite the matching root hash, it would see this
+        let b_children_mutation = &mut b.children[0];
+        b_children_mutation.title = "This should never be inspected".to_string();
+
+        let mut diff = TreeDiff::default();
+        diff_level(&[a], &[b], &mut diff);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn diff_level_flags_changed_hash_and_missing_hash() {
+        let mut a = bookmark("n1", "Same", "https://example.com");
+        a.hash = Some("hash-a".to_string());
+        let mut b = a.clone();
+        b.hash = Some("hash-b".to_string());
+
+        let mut diff = TreeDiff::default();
+        diff_level(&[a.clone()], &[b], &mut diff);
+        assert_eq!(diff.changed.len(), 1);
+
+        // Nodes without a hash can't be trusted as unchanged, even if identical otherwise
+        let mut diff_no_hash = TreeDiff::default();
+        let mut a_no_hash = a.clone();
+        a_no_hash.hash = None;
+        let mut b_no_hash = a.clone();
+        b_no_hash.hash = None;
+        diff_level(&[a_no_hash], &[b_no_hash], &mut diff_no_hash);
+        assert_eq!(diff_no_hash.changed.len(), 1);
+    }
+
+    #[test]
+    fn all_hashed_detects_missing_hash_anywhere_in_the_tree() {
+        let mut hashed_parent = bookmark("n1", "Parent", "https://example.com");
+        hashed_parent.hash = Some("h1".to_string());
+        assert!(all_hashed(&[hashed_parent.clone()]));
+
+        let mut unhashed_child = bookmark("child", "Child", "https://example.com/child");
+        unhashed_child.hash = None;
+        let mut parent_with_unhashed_child = hashed_parent.clone();
+        parent_with_unhashed_child.children.push(unhashed_child);
+        assert!(!all_hashed(&[parent_with_unhashed_child]));
+    }
+
+    #[test]
+    fn registrable_domain_tag_handles_multi_part_public_suffixes() {
+        assert_eq!(registrable_domain_tag("bbc.co.uk"), "bbc");
+        assert_eq!(registrable_domain_tag("docs.github.com"), "github");
+        assert_eq!(registrable_domain_tag("example.com"), "example");
+    }
+
+    #[test]
+    fn normalize_url_unifies_scheme_and_strips_tracking_params() {
+        let a = normalize_url("http://Example.com/page?utm_source=x", false);
+        let b = normalize_url("https://example.com/page/", false);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn normalize_url_exact_match_bypasses_normalization() {
+        let a = normalize_url("http://Example.com/page?utm_source=x", true);
+        let b = normalize_url("https://example.com/page/", true);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn move_node_in_tree_rejects_moving_a_node_into_itself() {
+        let mut nodes = vec![folder("a", "A")];
+        let before = nodes.clone();
+
+        let result = move_node_in_tree(&mut nodes, "a", "a");
+
+        assert!(result.is_err());
+        assert_eq!(nodes, before);
+    }
+
+    #[test]
+    fn move_node_in_tree_rejects_moving_a_node_into_its_own_descendant() {
+        let mut child = folder("child", "Child");
+        child.children.push(folder("grandchild", "Grandchild"));
+        let mut root = folder("parent", "Parent");
+        root.children.push(child);
+        let mut nodes = vec![root];
+        let before = nodes.clone();
+
+        let result = move_node_in_tree(&mut nodes, "parent", "grandchild");
+
+        assert!(result.is_err());
+        assert_eq!(nodes, before);
+    }
+
+    #[test]
+    fn move_node_in_tree_rejects_a_missing_parent_without_mutating() {
+        let mut nodes = vec![folder("a", "A"), folder("b", "B")];
+        let before = nodes.clone();
+
+        let result = move_node_in_tree(&mut nodes, "a", "does-not-exist");
+
+        assert!(result.is_err());
+        assert_eq!(nodes, before);
+    }
+
+    #[test]
+    fn validate_tree_flags_duplicate_id() {
+        let nodes = vec![bookmark("dup", "One", "https://example.com/1"), bookmark("dup", "Two", "https://example.com/2")];
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &HashMap::new(), &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "duplicate_id"));
+    }
+
+    #[test]
+    fn validate_tree_flags_empty_title() {
+        let nodes = vec![bookmark("n1", "   ", "https://example.com")];
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &HashMap::new(), &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "empty_title"));
+    }
+
+    #[test]
+    fn validate_tree_flags_folder_with_only_duplicates() {
+        let mut parent = folder("parent", "Parent");
+        let mut child = bookmark("child", "Child", "https://example.com");
+        child.is_duplicate = true;
+        parent.children.push(child);
+        let nodes = vec![parent];
+
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &HashMap::new(), &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "only_duplicates"));
+    }
+
+    #[test]
+    fn validate_tree_flags_empty_url() {
+        let nodes = vec![bookmark("n1", "No URL", "")];
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &HashMap::new(), &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "empty_url"));
+    }
+
+    #[test]
+    fn validate_tree_flags_javascript_uri() {
+        let nodes = vec![bookmark("n1", "Evil", "javascript:alert(1)")];
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &HashMap::new(), &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "javascript_uri"));
+    }
+
+    #[test]
+    fn validate_tree_flags_invalid_scheme() {
+        let nodes = vec![bookmark("n1", "FTP", "ftp://example.com/file")];
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &HashMap::new(), &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "invalid_scheme"));
+    }
+
+    #[test]
+    fn validate_tree_flags_dead_link() {
+        let nodes = vec![bookmark("n1", "Example", "https://example.com")];
+        let mut link_statuses = HashMap::new();
+        link_statuses.insert("https://example.com".to_string(), 404u16);
+
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &link_statuses, &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "dead_link"));
+    }
+
+    #[test]
+    fn validate_tree_flags_orphaned_duplicate() {
+        let mut node = bookmark("n1", "Example", "https://example.com");
+        node.is_duplicate = true;
+        let nodes = vec![node];
+
+        let mut issues = Vec::new();
+        walk_validate(&nodes, &mut std::collections::HashSet::new(), &HashMap::new(), &mut issues);
+        assert!(issues.iter().any(|i| i.kind == "orphaned_duplicate"));
+    }
+
+    #[test]
+    fn render_html_escapes_unsafe_title_characters() {
+        let nodes = vec![bookmark("n1", "A & B \"quote\" <x>", "https://example.com")];
+        let html = render_html(&nodes);
+
+        assert!(!html.contains("<x>"));
+        assert!(html.contains("A &amp; B \"quote\" &lt;x&gt;"));
+    }
+
+    #[test]
+    fn render_csv_escapes_embedded_quotes_in_title() {
+        let nodes = vec![bookmark("n1", "A & B \"quote\" <x>", "https://example.com")];
+        let csv = render_csv(&nodes);
+
+        assert!(csv.contains("\"A & B \"\"quote\"\" <x>\""));
+    }
 }